@@ -0,0 +1,259 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    io::BufRead,
+    str::FromStr,
+};
+
+/// An HTTP request method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HTTPMethod {
+    GET,
+    POST,
+    PUT,
+    DELETE,
+}
+
+impl FromStr for HTTPMethod {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<HTTPMethod, Self::Err> {
+        match s {
+            "GET" => Ok(HTTPMethod::GET),
+            "POST" => Ok(HTTPMethod::POST),
+            "PUT" => Ok(HTTPMethod::PUT),
+            "DELETE" => Ok(HTTPMethod::DELETE),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A parsed HTTP request: the request line, a case-insensitive header map,
+/// and an optional body.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: HTTPMethod,
+    pub path: String,
+    pub version: String,
+    headers: HashMap<String, String>,
+    pub body: Option<Vec<u8>>,
+}
+
+impl Request {
+    /// Builds a `Request` from an already-parsed request line, with no
+    /// headers or body.
+    pub fn new(method: HTTPMethod, path: impl Into<String>, version: impl Into<String>) -> Request {
+        Request {
+            method,
+            path: path.into(),
+            version: version.into(),
+            headers: HashMap::new(),
+            body: None,
+        }
+    }
+
+    /// Looks up a header by name, case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_ascii_lowercase()).map(String::as_str)
+    }
+
+    /// Inserts a header, overwriting any existing value (case-insensitively).
+    pub fn insert_header(&mut self, name: impl AsRef<str>, value: impl Into<String>) {
+        self.headers.insert(name.as_ref().to_ascii_lowercase(), value.into());
+    }
+
+    /// Reads a full HTTP request off `reader`: the request line, headers up
+    /// to the blank line that ends them, and — if a `Content-Length` header
+    /// is present — exactly that many bytes of body.
+    pub fn parse<R: BufRead>(reader: &mut R) -> Result<Request, ParseError> {
+        let request_line = read_line(reader)?;
+        let parts: Vec<&str> = request_line.split(' ').collect();
+        let [method, path, version] = parts[..] else {
+            return Err(ParseError::MalformedRequestLine);
+        };
+        let method = method
+            .parse::<HTTPMethod>()
+            .map_err(|_| ParseError::MalformedRequestLine)?;
+
+        let mut request = Request::new(method, path, version);
+
+        loop {
+            let line = read_line(reader)?;
+            if line.is_empty() {
+                break;
+            }
+            let (name, value) = line.split_once(':').ok_or(ParseError::BadHeader)?;
+            request.insert_header(name.trim(), value.trim());
+        }
+
+        if let Some(length) = request.header("content-length") {
+            let length: usize = length.parse().map_err(|_| ParseError::BadHeader)?;
+            let mut body = vec![0u8; length];
+            reader.read_exact(&mut body).map_err(|_| ParseError::UnexpectedEof)?;
+            request.body = Some(body);
+        }
+
+        Ok(request)
+    }
+}
+
+/// Reads a single `\r\n`-or-`\n`-terminated line, with the terminator
+/// stripped. Treats EOF before any bytes are read as
+/// [`ParseError::UnexpectedEof`].
+fn read_line<R: BufRead>(reader: &mut R) -> Result<String, ParseError> {
+    let mut line = String::new();
+    let bytes_read = reader.read_line(&mut line).map_err(|_| ParseError::UnexpectedEof)?;
+    if bytes_read == 0 {
+        return Err(ParseError::UnexpectedEof);
+    }
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Errors that can occur while parsing a request with [`Request::parse`].
+#[derive(Debug)]
+pub enum ParseError {
+    /// The request line didn't have the form `METHOD PATH VERSION`, or the
+    /// method wasn't recognized.
+    MalformedRequestLine,
+    /// A header line wasn't of the form `Name: value`, or a header's value
+    /// couldn't be parsed (e.g. a non-numeric `Content-Length`).
+    BadHeader,
+    /// The stream ended before a full request line, the header block, or
+    /// the declared body could be read.
+    UnexpectedEof,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MalformedRequestLine => write!(f, "malformed request line"),
+            ParseError::BadHeader => write!(f, "malformed header"),
+            ParseError::UnexpectedEof => write!(f, "unexpected end of stream"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn parse(input: &str) -> Result<Request, ParseError> {
+        Request::parse(&mut Cursor::new(input.as_bytes()))
+    }
+
+    #[test]
+    fn parses_request_line_headers_and_body() {
+        let request = parse("POST /greet HTTP/1.1\r\nContent-Length: 5\r\nX-Trace-Id: abc\r\n\r\nhello").unwrap();
+
+        assert_eq!(request.method, HTTPMethod::POST);
+        assert_eq!(request.path, "/greet");
+        assert_eq!(request.version, "HTTP/1.1");
+        assert_eq!(request.header("x-trace-id"), Some("abc"));
+        assert_eq!(request.body, Some(b"hello".to_vec()));
+    }
+
+    #[test]
+    fn parses_request_with_no_body_when_content_length_is_absent() {
+        let request = parse("GET / HTTP/1.1\r\n\r\n").unwrap();
+
+        assert_eq!(request.method, HTTPMethod::GET);
+        assert_eq!(request.body, None);
+    }
+
+    #[test]
+    fn rejects_malformed_request_line() {
+        let err = parse("GET /\r\n\r\n").unwrap_err();
+        assert!(matches!(err, ParseError::MalformedRequestLine));
+    }
+
+    #[test]
+    fn rejects_unrecognized_method() {
+        let err = parse("PATCH / HTTP/1.1\r\n\r\n").unwrap_err();
+        assert!(matches!(err, ParseError::MalformedRequestLine));
+    }
+
+    #[test]
+    fn rejects_header_line_with_no_colon() {
+        let err = parse("GET / HTTP/1.1\r\nnot-a-header\r\n\r\n").unwrap_err();
+        assert!(matches!(err, ParseError::BadHeader));
+    }
+
+    #[test]
+    fn rejects_non_numeric_content_length() {
+        let err = parse("GET / HTTP/1.1\r\nContent-Length: nope\r\n\r\n").unwrap_err();
+        assert!(matches!(err, ParseError::BadHeader));
+    }
+
+    #[test]
+    fn rejects_body_shorter_than_content_length() {
+        let err = parse("GET / HTTP/1.1\r\nContent-Length: 10\r\n\r\nshort").unwrap_err();
+        assert!(matches!(err, ParseError::UnexpectedEof));
+    }
+
+    #[test]
+    fn rejects_empty_input() {
+        let err = parse("").unwrap_err();
+        assert!(matches!(err, ParseError::UnexpectedEof));
+    }
+}
+
+/// An HTTP response: a status, a header map, and a body.
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub reason: String,
+    headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    /// Builds a response with the given status code, reason phrase, and body.
+    pub fn new(status: u16, reason: impl Into<String>, body: impl Into<Vec<u8>>) -> Response {
+        Response {
+            status,
+            reason: reason.into(),
+            headers: HashMap::new(),
+            body: body.into(),
+        }
+    }
+
+    /// A `200 OK` response with the given body.
+    pub fn ok(body: impl Into<Vec<u8>>) -> Response {
+        Response::new(200, "OK", body)
+    }
+
+    /// A `404 Not Found` response with the given body.
+    pub fn not_found(body: impl Into<Vec<u8>>) -> Response {
+        Response::new(404, "NOT FOUND", body)
+    }
+
+    /// A `400 Bad Request` response with the given body.
+    pub fn bad_request(body: impl Into<Vec<u8>>) -> Response {
+        Response::new(400, "BAD REQUEST", body)
+    }
+
+    /// Sets a header, overwriting any existing value (case-insensitively).
+    pub fn with_header(mut self, name: impl AsRef<str>, value: impl Into<String>) -> Response {
+        self.headers.insert(name.as_ref().to_ascii_lowercase(), value.into());
+        self
+    }
+
+    /// Serializes the response as raw HTTP/1.1 bytes: the status line,
+    /// `Content-Length`, any custom headers, and the body.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut head = format!("HTTP/1.1 {} {}\r\n", self.status, self.reason);
+
+        head.push_str(&format!("Content-Length: {}\r\n", self.body.len()));
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+        head.push_str("\r\n");
+
+        let mut bytes = head.into_bytes();
+        bytes.extend_from_slice(&self.body);
+        bytes
+    }
+}
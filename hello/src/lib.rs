@@ -1,49 +1,200 @@
 use std::{
-    sync::{mpsc, Arc, Mutex},
+    fmt,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc, Mutex,
+    },
     thread,
 };
 
+mod http;
+mod metrics;
+mod router;
+mod server;
+
+pub use http::{HTTPMethod, ParseError, Request, Response};
+pub use metrics::Metrics;
+pub use router::{Handler, Router};
+pub use server::Server;
+
 type JoinHandle = thread::JoinHandle<()>;
 type Job = Box<dyn FnOnce() + Send + 'static>;
 
+/// Errors that can occur while building a [`ThreadPool`].
+#[derive(Debug)]
+pub enum PoolCreationError {
+    /// The requested number of workers was 0.
+    ZeroSize,
+    /// Spawning a worker thread failed.
+    SpawnFailed(std::io::Error),
+}
+
+impl fmt::Display for PoolCreationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoolCreationError::ZeroSize => write!(f, "a ThreadPool needs at least one worker"),
+            PoolCreationError::SpawnFailed(err) => write!(f, "failed to spawn worker thread: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for PoolCreationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PoolCreationError::ZeroSize => None,
+            PoolCreationError::SpawnFailed(err) => Some(err),
+        }
+    }
+}
+
+/// Either end of the channel workers pull jobs from, depending on whether
+/// the pool was built with a bounded queue capacity.
+enum JobSender {
+    Unbounded(mpsc::Sender<Job>),
+    Bounded(mpsc::SyncSender<Job>),
+}
+
+impl JobSender {
+    fn send(&self, job: Job) -> Result<(), mpsc::SendError<Job>> {
+        match self {
+            JobSender::Unbounded(sender) => sender.send(job),
+            JobSender::Bounded(sender) => sender.send(job),
+        }
+    }
+}
+
+/// Builds a [`ThreadPool`] with a configurable worker count, thread name
+/// prefix, and job-queue capacity.
+///
+/// # Examples
+/// ```
+/// use hello::ThreadPool;
+///
+/// let pool = ThreadPool::builder()
+///     .workers(4)
+///     .thread_name_prefix("worker")
+///     .queue_capacity(16)
+///     .build()
+///     .unwrap();
+/// ```
+pub struct ThreadPoolBuilder {
+    n_workers: usize,
+    thread_name_prefix: Option<String>,
+    queue_capacity: Option<usize>,
+}
+
+impl ThreadPoolBuilder {
+    fn new() -> ThreadPoolBuilder {
+        ThreadPoolBuilder {
+            n_workers: 4,
+            thread_name_prefix: None,
+            queue_capacity: None,
+        }
+    }
+
+    /// Sets the number of worker threads. Defaults to 4.
+    pub fn workers(mut self, n_workers: usize) -> ThreadPoolBuilder {
+        self.n_workers = n_workers;
+        self
+    }
+
+    /// Sets a prefix used to name each worker thread (e.g. `"worker-0"`).
+    pub fn thread_name_prefix(mut self, prefix: impl Into<String>) -> ThreadPoolBuilder {
+        self.thread_name_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Bounds the job queue to `capacity` pending jobs, so that `execute`
+    /// blocks (applying backpressure) once the queue is full instead of
+    /// growing it unboundedly.
+    pub fn queue_capacity(mut self, capacity: usize) -> ThreadPoolBuilder {
+        self.queue_capacity = Some(capacity);
+        self
+    }
+
+    /// Builds the `ThreadPool`, or returns a [`PoolCreationError`] if a
+    /// worker thread could not be spawned.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PoolCreationError::ZeroSize` if the worker count is 0, or
+    /// `PoolCreationError::SpawnFailed` if spawning a worker thread fails.
+    pub fn build(self) -> Result<ThreadPool, PoolCreationError> {
+        if self.n_workers == 0 {
+            return Err(PoolCreationError::ZeroSize);
+        }
+
+        let (sender, receiver) = match self.queue_capacity {
+            Some(capacity) => {
+                let (tx, rx) = mpsc::sync_channel(capacity);
+                (JobSender::Bounded(tx), rx)
+            }
+            None => {
+                let (tx, rx) = mpsc::channel();
+                (JobSender::Unbounded(tx), rx)
+            }
+        };
+
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let mut workers = Vec::with_capacity(self.n_workers);
+
+        for id in 0..self.n_workers {
+            let name = self
+                .thread_name_prefix
+                .as_ref()
+                .map(|prefix| format!("{prefix}-{id}"));
+
+            workers.push(Worker::new(id, Arc::clone(&receiver), name).map_err(PoolCreationError::SpawnFailed)?);
+        }
+
+        Ok(ThreadPool {
+            workers,
+            sender: Some(sender),
+        })
+    }
+}
+
 pub struct ThreadPool {
     workers: Vec<Worker>,
-    sender: Option<mpsc::Sender<Job>>,
+    sender: Option<JobSender>,
 }
 
 impl ThreadPool {
-    /// Create a new `ThreadPool`.
+    /// Create a new `ThreadPool` with `n_workers` worker threads and an
+    /// unbounded job queue.
     ///
-    /// The size is the number of threads in the pool.
+    /// # Errors
     ///
-    /// # Panics
-    ///
-    /// Will panic if `n_workers` is 0.
+    /// Returns `PoolCreationError::ZeroSize` if `n_workers` is 0.
     ///
     /// # Examples
     /// ```
     /// use hello::ThreadPool;
     ///
     /// // Create a ThreadPool with 4 workers
-    /// let mut pool = ThreadPool::new(4);
+    /// let pool = ThreadPool::new(4).unwrap();
     /// ```
-    pub fn new(n_workers: usize) -> ThreadPool {
-        assert!(n_workers > 0);
-
-        let (tx, rx) = mpsc::channel();
-
-        let rx = Arc::new(Mutex::new(rx));
+    pub fn new(n_workers: usize) -> Result<ThreadPool, PoolCreationError> {
+        ThreadPoolBuilder::new().workers(n_workers).build()
+    }
 
-        let mut workers = Vec::with_capacity(n_workers);
+    /// Returns a [`ThreadPoolBuilder`] for configuring worker count, thread
+    /// name prefix, and job-queue capacity before building the pool.
+    pub fn builder() -> ThreadPoolBuilder {
+        ThreadPoolBuilder::new()
+    }
 
-        for id in 0..n_workers {
-            workers.push(Worker::new(id, Arc::clone(&rx)));
-        }
+    /// Returns the number of jobs completed and panicked for each worker,
+    /// in worker-id order.
+    pub fn stats(&self) -> Vec<WorkerStats> {
+        self.workers.iter().map(Worker::stats).collect()
+    }
 
-        return ThreadPool {
-            workers,
-            sender: Some(tx),
-        };
+    /// Returns the number of worker threads in the pool.
+    pub fn worker_count(&self) -> usize {
+        self.workers.len()
     }
 
     /// Executes the given `Job` on a free worker from the `ThreadPool`.
@@ -54,7 +205,7 @@ impl ThreadPool {
     /// use hello::ThreadPool;
     /// use std::sync::{Arc, Mutex};
     ///
-    /// let mut pool = ThreadPool::new(4);
+    /// let mut pool = ThreadPool::new(4).unwrap();
     /// let counter = Arc::new(Mutex::new(0));
     ///
     /// for _ in (0..4) {
@@ -98,37 +249,102 @@ impl Drop for ThreadPool {
     }
 }
 
+/// A snapshot of a single worker's job counts, as returned by
+/// [`ThreadPool::stats`].
+#[derive(Debug, Clone, Copy)]
+pub struct WorkerStats {
+    pub id: usize,
+    pub jobs_completed: usize,
+    pub jobs_panicked: usize,
+}
+
 struct Worker {
     id: usize,
     thread: Option<JoinHandle>,
+    jobs_completed: Arc<AtomicUsize>,
+    jobs_panicked: Arc<AtomicUsize>,
 }
 
 impl Worker {
-    /// Create a new `Worker` that will wait for tasks while it has a `thread`.
-    fn new(id: usize, receiver: Arc<Mutex<mpsc::Receiver<Job>>>) -> Worker {
-        let thread = thread::spawn(move || loop {
-            // Unwrap to panic in case another thread panicked.
-            let receiver = receiver.lock().unwrap();
-            let message = receiver.recv();
-
-            drop(receiver);
-
-            match message {
-                Ok(job) => {
-                    println!("Worker {id} got a job; executing.");
-                    job();
-                    println!("Worker {id} finished job; waiting.");
-                }
-                Err(_) => {
-                    println!("Worker {id} discconected; shutting down.");
-                    break;
+    /// Create a new `Worker` that will wait for tasks while it has a `thread`,
+    /// optionally named via `name`.
+    ///
+    /// A job that panics is caught so the worker keeps serving instead of
+    /// dying; the panic payload is logged with the worker id.
+    fn new(
+        id: usize,
+        receiver: Arc<Mutex<mpsc::Receiver<Job>>>,
+        name: Option<String>,
+    ) -> std::io::Result<Worker> {
+        let mut builder = thread::Builder::new();
+        if let Some(name) = name {
+            builder = builder.name(name);
+        }
+
+        let jobs_completed = Arc::new(AtomicUsize::new(0));
+        let jobs_panicked = Arc::new(AtomicUsize::new(0));
+
+        let thread = {
+            let jobs_completed = Arc::clone(&jobs_completed);
+            let jobs_panicked = Arc::clone(&jobs_panicked);
+
+            builder.spawn(move || loop {
+                // A poisoned mutex just means some other worker panicked
+                // while holding the lock, not that the receiver is broken,
+                // so keep using it rather than propagating the poison.
+                let receiver = receiver.lock().unwrap_or_else(|e| e.into_inner());
+                let message = receiver.recv();
+
+                drop(receiver);
+
+                match message {
+                    Ok(job) => {
+                        println!("Worker {id} got a job; executing.");
+
+                        match catch_unwind(AssertUnwindSafe(job)) {
+                            Ok(()) => {
+                                jobs_completed.fetch_add(1, Ordering::Relaxed);
+                                println!("Worker {id} finished job; waiting.");
+                            }
+                            Err(payload) => {
+                                jobs_panicked.fetch_add(1, Ordering::Relaxed);
+                                let message = panic_message(&payload);
+                                eprintln!("Worker {id} job panicked: {message}");
+                            }
+                        }
+                    }
+                    Err(_) => {
+                        println!("Worker {id} discconected; shutting down.");
+                        break;
+                    }
                 }
-            }
-        });
+            })?
+        };
 
-        return Worker {
+        Ok(Worker {
             id,
             thread: Some(thread),
-        };
+            jobs_completed,
+            jobs_panicked,
+        })
+    }
+
+    fn stats(&self) -> WorkerStats {
+        WorkerStats {
+            id: self.id,
+            jobs_completed: self.jobs_completed.load(Ordering::Relaxed),
+            jobs_panicked: self.jobs_panicked.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Extracts a human-readable message from a `catch_unwind` panic payload.
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "Box<dyn Any>"
     }
 }
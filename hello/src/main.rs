@@ -1,87 +1,63 @@
 use std::{
-    fs,
-    io::{BufRead, BufReader, Write},
-    net::{TcpListener, TcpStream},
-    str::FromStr,
+    io::{BufReader, Write},
+    net::TcpStream,
+    sync::Arc,
     thread,
     time::Duration,
 };
 
-use hello::ThreadPool;
+use hello::{HTTPMethod, Metrics, Request, Response, Router, Server};
 
-#[derive(Debug, PartialEq, Eq)]
-enum HTTPMethod {
-    GET,
-    POST,
-    PUT,
-    DELETE,
-}
-
-impl FromStr for HTTPMethod {
-    type Err = ();
+const N_WORKERS: usize = 4;
 
-    fn from_str(s: &str) -> Result<HTTPMethod, Self::Err> {
-        match s {
-            "GET" => Ok(HTTPMethod::GET),
-            "POST" => Ok(HTTPMethod::POST),
-            "PUT" => Ok(HTTPMethod::PUT),
-            "DELETE" => Ok(HTTPMethod::DELETE),
-            _ => Err(()),
+fn main() {
+    let metrics = Arc::new(Metrics::new());
+
+    let mut router = Router::new();
+    router.static_file(HTTPMethod::GET, "/", "src/hello.html");
+    router.route(HTTPMethod::GET, "/sleep", |_: &Request| {
+        thread::sleep(Duration::from_secs(5));
+        match std::fs::read("src/hello.html") {
+            Ok(contents) => Response::ok(contents),
+            Err(err) => Response::new(500, "INTERNAL SERVER ERROR", format!("{err}")),
         }
+    });
+    router.fallback(|_: &Request| match std::fs::read("src/404.html") {
+        Ok(contents) => Response::not_found(contents),
+        Err(err) => Response::new(500, "INTERNAL SERVER ERROR", format!("{err}")),
+    });
+    {
+        let metrics = Arc::clone(&metrics);
+        router.route(HTTPMethod::GET, "/metrics", move |_: &Request| Response::ok(metrics.report()));
     }
-}
 
-fn main() {
-    let listener =
-        TcpListener::bind("127.0.0.1:7878").expect("Should be able to bind to ip 127.0.0.1:7878");
-    let mut thread_pool = ThreadPool::new(4);
+    let server = Server::bind("127.0.0.1:7878", N_WORKERS, Arc::clone(&metrics), move |stream| {
+        handle_connection(stream, &router, &metrics)
+    })
+    .expect("Should be able to bind to 127.0.0.1:7878");
 
-    for stream in listener.incoming() {
-        match stream {
-            Ok(stream) => thread_pool.execute(|| handle_connection(stream)),
-            Err(err) => eprintln!("Connection failed: {err:#?}"),
-        };
-    }
+    server.run();
 }
 
-fn handle_connection(mut stream: TcpStream) {
-    let buf_reader = BufReader::new(&mut stream);
-    let http_request = match buf_reader.lines().next() {
-        Some(Ok(http_request)) => http_request,
-        Some(Err(err)) => {
-            eprintln!("Found error while reading from buffer. {err:#?}");
-            return;
-        }
-        None => {
-            eprintln!("Invalid HTTP request line.");
-            return;
-        }
-    };
-
-    let http_request: Vec<&str> = http_request.split(" ").collect();
+fn handle_connection(mut stream: TcpStream, router: &Router, metrics: &Metrics) {
+    let mut buf_reader = BufReader::new(&mut stream);
 
-    let [method, route, _version] = http_request[..] else {
-        eprintln!("Invalid HTTP request line.");
-        return;
-    };
-
-    println!("{method} {route}");
-
-    let (status_line, filename) = match (method, route) {
-        ("GET", "/") => ("HTTP/1.1 200 OK", "src/hello.html"),
-        ("GET", "/sleep") => {
-            thread::sleep(Duration::from_secs(5));
-            ("HTTP/1.1 200 OK", "src/hello.html")
+    let response = match Request::parse(&mut buf_reader) {
+        Ok(request) => {
+            println!("{:?} {}", request.method, request.path);
+            router.handle(&request)
+        }
+        Err(err) => {
+            eprintln!("Failed to parse request: {err}");
+            metrics.record_error();
+            Response::bad_request(format!("{err}"))
         }
-        _ => ("HTTP/1.1 404 NOT FOUND", "src/404.html"),
     };
 
-    // The file filename should exist as it's defined in the code above.
-    let contents = fs::read_to_string(filename).unwrap();
-    let length = contents.len();
+    metrics.record_response(response.status);
 
-    let response = format!("{status_line}\r\nContent-Length: {length}\r\n\r\n{contents}");
-    if let Err(err) = stream.write_all(response.as_bytes()) {
+    if let Err(err) = stream.write_all(&response.to_bytes()) {
         eprintln!("Couldn't send response.\nError: {err:#?}");
+        metrics.record_error();
     };
 }
@@ -0,0 +1,74 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+/// Shared counters tracking server throughput and error rates, updated from
+/// the accept loop and from `handle_connection`.
+#[derive(Default)]
+pub struct Metrics {
+    connections_accepted: AtomicUsize,
+    requests_completed: AtomicUsize,
+    requests_errored: AtomicUsize,
+    status_counts: Mutex<HashMap<u16, usize>>,
+    worker_count: AtomicUsize,
+}
+
+impl Metrics {
+    /// Creates a `Metrics` with every counter at 0.
+    pub fn new() -> Metrics {
+        Metrics::default()
+    }
+
+    /// Records that a connection was accepted.
+    pub fn record_connection_accepted(&self) {
+        self.connections_accepted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records the pool's worker count, so `report` reflects the server it's
+    /// actually attached to rather than a value the caller has to track
+    /// separately.
+    pub fn set_worker_count(&self, worker_count: usize) {
+        self.worker_count.store(worker_count, Ordering::Relaxed);
+    }
+
+    /// Records that a response with `status` was produced for a request,
+    /// whether or not writing it to the client then succeeds.
+    pub fn record_response(&self, status: u16) {
+        self.requests_completed.fetch_add(1, Ordering::Relaxed);
+
+        let mut status_counts = self.status_counts.lock().unwrap_or_else(|e| e.into_inner());
+        *status_counts.entry(status).or_insert(0) += 1;
+    }
+
+    /// Records a connection-level failure: a malformed request or a failed
+    /// write, as opposed to an application-level non-2xx status.
+    pub fn record_error(&self) {
+        self.requests_errored.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Renders a small plaintext report: connection/request counts,
+    /// per-status-code counts, and the pool's worker count.
+    pub fn report(&self) -> String {
+        let mut report = format!(
+            "connections_accepted {}\nrequests_completed {}\nrequests_errored {}\nworker_count {}\n",
+            self.connections_accepted.load(Ordering::Relaxed),
+            self.requests_completed.load(Ordering::Relaxed),
+            self.requests_errored.load(Ordering::Relaxed),
+            self.worker_count.load(Ordering::Relaxed),
+        );
+
+        let status_counts = self.status_counts.lock().unwrap_or_else(|e| e.into_inner());
+        let mut statuses: Vec<_> = status_counts.iter().collect();
+        statuses.sort_by_key(|(status, _)| **status);
+
+        for (status, count) in statuses {
+            report.push_str(&format!("status_{status} {count}\n"));
+        }
+
+        report
+    }
+}
@@ -0,0 +1,60 @@
+use std::{collections::HashMap, fs, sync::Arc};
+
+use crate::http::{HTTPMethod, Request, Response};
+
+/// A handler that turns a [`Request`] into a [`Response`].
+pub trait Handler: Fn(&Request) -> Response + Send + Sync {}
+impl<F: Fn(&Request) -> Response + Send + Sync> Handler for F {}
+
+/// Dispatches requests to handlers registered by `(method, path)`, falling
+/// back to a 404 handler when nothing matches.
+pub struct Router {
+    routes: HashMap<(HTTPMethod, String), Arc<dyn Handler>>,
+    fallback: Arc<dyn Handler>,
+}
+
+impl Router {
+    /// Creates a `Router` whose fallback is a plain `404 Not Found`.
+    pub fn new() -> Router {
+        Router {
+            routes: HashMap::new(),
+            fallback: Arc::new(|_: &Request| Response::not_found("Not Found")),
+        }
+    }
+
+    /// Registers `handler` to serve `method` requests to `path`.
+    pub fn route(&mut self, method: HTTPMethod, path: impl Into<String>, handler: impl Handler + 'static) {
+        self.routes.insert((method, path.into()), Arc::new(handler));
+    }
+
+    /// Registers `handler` as the response for any request that doesn't
+    /// match a registered route, replacing the default 404.
+    pub fn fallback(&mut self, handler: impl Handler + 'static) {
+        self.fallback = Arc::new(handler);
+    }
+
+    /// Registers a route that serves the contents of `file_path` verbatim
+    /// with a `200 OK` status.
+    pub fn static_file(&mut self, method: HTTPMethod, path: impl Into<String>, file_path: impl Into<String>) {
+        let file_path = file_path.into();
+        self.route(method, path, move |_: &Request| match fs::read(&file_path) {
+            Ok(contents) => Response::ok(contents),
+            Err(err) => Response::new(500, "INTERNAL SERVER ERROR", format!("{err}")),
+        });
+    }
+
+    /// Dispatches `request` to its registered handler, or the fallback if
+    /// no route matches `(request.method, request.path)`.
+    pub fn handle(&self, request: &Request) -> Response {
+        match self.routes.get(&(request.method, request.path.clone())) {
+            Some(handler) => handler(request),
+            None => (self.fallback)(request),
+        }
+    }
+}
+
+impl Default for Router {
+    fn default() -> Router {
+        Router::new()
+    }
+}
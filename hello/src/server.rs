@@ -0,0 +1,155 @@
+use std::{
+    io,
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+use crate::{Metrics, ThreadPool};
+
+/// How long to sleep after a non-blocking `accept()` finds no pending
+/// connection, before re-checking the shutdown flag.
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A minimal SIGINT handler, implemented via a direct `extern "C"` binding
+/// to libc's `signal` so the crate doesn't need an external dependency for
+/// something this small.
+mod sigint {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    const SIGINT: i32 = 2;
+
+    static REQUESTED: AtomicBool = AtomicBool::new(false);
+
+    extern "C" fn on_signal(_signum: i32) {
+        REQUESTED.store(true, Ordering::SeqCst);
+    }
+
+    extern "C" {
+        fn signal(signum: i32, handler: extern "C" fn(i32)) -> usize;
+    }
+
+    /// Installs the process-wide SIGINT handler. Safe to call more than
+    /// once; later calls just re-install the same handler.
+    pub fn install() {
+        unsafe {
+            signal(SIGINT, on_signal);
+        }
+    }
+
+    /// Returns whether SIGINT has been received since the process started.
+    pub fn requested() -> bool {
+        REQUESTED.load(Ordering::SeqCst)
+    }
+}
+
+/// Owns the listening socket and the `ThreadPool` that serves it, and can be
+/// stopped deterministically via Ctrl-C or a connection limit.
+///
+/// `H` is the per-connection handler, run on a pool worker for every
+/// accepted connection.
+pub struct Server<H> {
+    listener: TcpListener,
+    pool: ThreadPool,
+    handler: Arc<H>,
+    metrics: Arc<Metrics>,
+}
+
+impl<H> Server<H>
+where
+    H: Fn(TcpStream) + Send + Sync + 'static,
+{
+    /// Binds `addr`, builds a `ThreadPool` with `n_workers` workers, and
+    /// installs a SIGINT handler that requests a graceful shutdown.
+    pub fn bind(addr: &str, n_workers: usize, metrics: Arc<Metrics>, handler: H) -> io::Result<Server<H>> {
+        let listener = TcpListener::bind(addr)?;
+        // Accept() must return periodically so we can check for a shutdown
+        // request instead of blocking forever.
+        listener.set_nonblocking(true)?;
+
+        let pool = ThreadPool::new(n_workers)
+            .unwrap_or_else(|err| panic!("Should be able to create a ThreadPool: {err}"));
+        metrics.set_worker_count(pool.worker_count());
+
+        sigint::install();
+
+        Ok(Server {
+            listener,
+            pool,
+            handler: Arc::new(handler),
+            metrics,
+        })
+    }
+
+    /// Returns the address the server's listener is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Returns the number of worker threads serving this server's pool.
+    pub fn worker_count(&self) -> usize {
+        self.pool.worker_count()
+    }
+
+    /// Runs until SIGINT is received.
+    pub fn run(self) {
+        self.run_until(usize::MAX);
+    }
+
+    /// Runs until either SIGINT is received or `max_connections` have been
+    /// accepted. Stops accepting, prints a summary, then drops the pool so
+    /// in-flight jobs finish before this returns.
+    pub fn run_until(mut self, max_connections: usize) {
+        let mut accepted = 0;
+
+        while accepted < max_connections && !sigint::requested() {
+            match self.listener.accept() {
+                Ok((stream, _addr)) => {
+                    accepted += 1;
+                    self.metrics.record_connection_accepted();
+                    let handler = Arc::clone(&self.handler);
+                    self.pool.execute(move || handler(stream));
+                }
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                    thread::sleep(ACCEPT_POLL_INTERVAL);
+                }
+                Err(err) => eprintln!("Connection failed: {err:#?}"),
+            }
+        }
+
+        println!("Shutting down server after {accepted} connection(s).");
+        // Dropping `self.pool` here waits for queued jobs to finish.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn run_until_stops_after_max_connections() {
+        let handled = Arc::new(AtomicUsize::new(0));
+        let handled_in_handler = Arc::clone(&handled);
+
+        let server = Server::bind("127.0.0.1:0", 2, Arc::new(Metrics::new()), move |_stream: TcpStream| {
+            handled_in_handler.fetch_add(1, Ordering::SeqCst);
+        })
+        .expect("bind should succeed on an ephemeral port");
+
+        let addr = server.local_addr().expect("a bound listener should have a local addr");
+
+        let connector = thread::spawn(move || {
+            for _ in 0..2 {
+                TcpStream::connect(addr).expect("should be able to connect to the server");
+                thread::sleep(Duration::from_millis(10));
+            }
+        });
+
+        server.run_until(2);
+        connector.join().expect("connector thread should not panic");
+
+        assert_eq!(handled.load(Ordering::SeqCst), 2);
+    }
+}